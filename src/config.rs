@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+const APP_NAME: &str = "hep-notify";
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub server: String,
+    pub username: String,
+    pub password: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A single HEP district to monitor, identified by the same `dp`/`el` query params the
+/// website uses (what the tool used to call `HEP_CITY`/`HEP_OFFICE`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Area {
+    pub name: String,
+    pub city: String,
+    pub office: String,
+    #[serde(default)]
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub smtp: SmtpConfig,
+    pub areas: Vec<Area>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            smtp: SmtpConfig {
+                server: "smtp.gmail.com".to_string(),
+                username: "you@example.com".to_string(),
+                password: "app-password".to_string(),
+                from: "you@example.com".to_string(),
+                to: "you@example.com".to_string(),
+            },
+            areas: vec![Area {
+                name: "Home".to_string(),
+                city: "11".to_string(),
+                office: "1".to_string(),
+                filter: None,
+            }],
+        }
+    }
+}
+
+impl Config {
+    /// Path to the config file under the XDG config directory, e.g. `~/.config/hep-notify/config.toml`.
+    pub fn path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(APP_NAME)
+            .join(CONFIG_FILE)
+    }
+
+    /// Loads the config file, writing a default template on first run if none exists yet.
+    /// Returns `Ok(None)` in that first-run case - the template is full of placeholder
+    /// credentials, so the caller must stop and let the user fill it in rather than scrape
+    /// and try to send mail with it.
+    pub fn load() -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let path = Self::path();
+
+        if !path.exists() {
+            let default = Config::default();
+            default.write_to(&path)?;
+            println!("📝 No config found - wrote a starter template to {}", path.display());
+            println!("   Edit it with your SMTP credentials and monitored areas, then re-run.");
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        let config: Config = toml::from_str(&contents)?;
+        Ok(Some(config))
+    }
+
+    fn write_to(&self, path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(self)?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone};
+use rayon::prelude::*;
+use scraper::{Html, Selector};
+
+use crate::config::Area;
+use crate::PowerOutage;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
+
+/// One (area, date) combination to scrape.
+pub struct FetchTask<'a> {
+    pub area: &'a Area,
+    pub date: String,
+}
+
+/// Outcome of scraping a single `FetchTask`. Kept as a `Result` per task rather than bailing
+/// the whole run, so one bad date/location can't abort everything else.
+pub struct FetchResult {
+    pub area: String,
+    pub date: String,
+    pub outages: Result<Vec<PowerOutage>, String>,
+}
+
+/// A simple token-bucket limiter that enforces a minimum spacing between requests, so a
+/// bounded worker pool fetching concurrently still stays polite to hep.hr.
+pub struct RateLimiter {
+    min_interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests_per_second: f64) -> Self {
+        let min_interval = Duration::from_secs_f64(1.0 / max_requests_per_second.max(0.01));
+        RateLimiter {
+            min_interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Blocks the calling thread until it's this caller's turn to make a request.
+    fn acquire(&self) {
+        let wait_until = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.min_interval;
+            slot
+        };
+
+        let now = Instant::now();
+        if wait_until > now {
+            std::thread::sleep(wait_until - now);
+        }
+    }
+}
+
+/// Runs every `FetchTask` across rayon's bounded global worker pool, with `limiter` capping
+/// the aggregate request rate against hep.hr. Results come back in a `Vec` keyed by task, so a
+/// failure on one (date, area) doesn't lose the others.
+pub fn fetch_all(tasks: Vec<FetchTask>, limiter: &RateLimiter) -> Vec<FetchResult> {
+    tasks
+        .into_par_iter()
+        .map(|task| {
+            limiter.acquire();
+
+            let outages = fetch_page(&task.date, &task.area.city, &task.area.office)
+                .map_err(|e| e.to_string())
+                .and_then(|html| parse_outages(&html, &task.area.name).map_err(|e| e.to_string()));
+
+            FetchResult {
+                area: task.area.name.clone(),
+                date: task.date.clone(),
+                outages,
+            }
+        })
+        .collect()
+}
+
+fn fetch_page(date: &str, city: &str, office: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let url = format!(
+        "https://www.hep.hr/ods/bez-struje/19?dp={}&el={}&datum={}",
+        city, office, date
+    );
+
+    let client = reqwest::blocking::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()?;
+
+    let response = client.get(&url).send()?;
+    let html = response.text()?;
+
+    Ok(html)
+}
+
+fn parse_outages(html: &str, area: &str) -> Result<Vec<PowerOutage>, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(html);
+    let mut outages = Vec::new();
+
+    // Find the date from the heading
+    let date_selector = Selector::parse("h3").unwrap();
+    let heading = document
+        .select(&date_selector)
+        .next()
+        .map(|el| el.text().collect::<String>())
+        .unwrap_or_default();
+    let date = extract_date(&heading);
+
+    // Each outage is in a section with hr separators
+    // Look for sections with "Mjesto:" pattern
+    let text = document.root_element().text().collect::<Vec<_>>().join(" ");
+
+    // Split by horizontal rules or look for "Mjesto:" patterns
+    let lines: Vec<&str> = text.split('\n').map(|s| s.trim()).collect();
+
+    let mut current_outage: Option<PowerOutage> = None;
+    let mut expect_time_next = false;
+
+    for line in lines {
+        if line.starts_with("Mjesto:") {
+            if let Some(outage) = current_outage.take() {
+                outages.push(finalize_outage(outage));
+            }
+            current_outage = Some(PowerOutage {
+                area: area.to_string(),
+                date: date.clone(),
+                location: line.replace("Mjesto:", "").trim().to_string(),
+                street: String::new(),
+                time: String::new(),
+                note: String::new(),
+                start: None,
+                end: None,
+            });
+            expect_time_next = false;
+        } else if line.starts_with("Ulica:") {
+            if let Some(ref mut outage) = current_outage {
+                outage.street = line.replace("Ulica:", "").trim().to_string();
+            }
+            expect_time_next = false;
+        } else if line.starts_with("Očekivano trajanje:") {
+            // The time might be on the same line or the next line
+            let time_on_same_line = line.replace("Očekivano trajanje:", "").trim().to_string();
+            if !time_on_same_line.is_empty() {
+                if let Some(ref mut outage) = current_outage {
+                    outage.time = time_on_same_line;
+                }
+                expect_time_next = false;
+            } else {
+                // Time is on the next line
+                expect_time_next = true;
+            }
+        } else if line.starts_with("Napomena:") {
+            if let Some(ref mut outage) = current_outage {
+                outage.note = line.replace("Napomena:", "").trim().to_string();
+            }
+            expect_time_next = false;
+        } else if expect_time_next && !line.is_empty() && line.contains("-") {
+            // This should be the time line (e.g., "09:00 - 11:30")
+            if let Some(ref mut outage) = current_outage {
+                outage.time = line.to_string();
+            }
+            expect_time_next = false;
+        }
+    }
+
+    // Don't forget the last one
+    if let Some(outage) = current_outage {
+        outages.push(finalize_outage(outage));
+    }
+
+    Ok(outages)
+}
+
+/// Pulls the bare "%d.%m.%Y" date out of the `<h3>` heading, which may carry surrounding text
+/// (e.g. "Popis kvarova za 01.08.2026"). `parse_outage_time` and `StateStore::prune` both parse
+/// `PowerOutage::date` with that strict format, so storing anything else silently breaks both.
+fn extract_date(heading: &str) -> String {
+    heading
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_digit() && c != '.'))
+        .find(|word| NaiveDate::parse_from_str(word, "%d.%m.%Y").is_ok())
+        .map(|word| word.to_string())
+        .unwrap_or_else(|| heading.trim().to_string())
+}
+
+/// Fills in the structured `start`/`end` timestamps now that an outage's `date` and `time`
+/// fields are fully populated.
+fn finalize_outage(mut outage: PowerOutage) -> PowerOutage {
+    if let Some((start, end)) = parse_outage_time(&outage.date, &outage.time) {
+        outage.start = Some(start);
+        outage.end = Some(end);
+    }
+    outage
+}
+
+/// Combines `date` ("%d.%m.%Y") with a "HH:MM - HH:MM" time range into concrete local
+/// timestamps, for use by the iCalendar export. Returns `None` if either half fails to parse.
+fn parse_outage_time(date: &str, time: &str) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let naive_date = NaiveDate::parse_from_str(date, "%d.%m.%Y").ok()?;
+
+    let mut parts = time.split('-').map(|s| s.trim());
+    let start_time = NaiveTime::parse_from_str(parts.next()?, "%H:%M").ok()?;
+    let end_time = NaiveTime::parse_from_str(parts.next()?, "%H:%M").ok()?;
+
+    let start = Local.from_local_datetime(&naive_date.and_time(start_time)).single()?;
+    let end = Local.from_local_datetime(&naive_date.and_time(end_time)).single()?;
+
+    Some((start, end))
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+
+use crate::state::fingerprint;
+use crate::PowerOutage;
+
+const ICS_DATETIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Writes an iCalendar (.ics) file with one `VEVENT` per outage that has structured start/end
+/// times. The `UID` is derived from the outage fingerprint so re-exporting after a re-scrape
+/// updates existing calendar entries instead of duplicating them.
+pub fn write_ics(outages: &[PowerOutage], path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//hep-notify//hep-outage-checker//EN");
+
+    for outage in outages {
+        let (Some(start), Some(end)) = (outage.start, outage.end) else {
+            continue;
+        };
+
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}@hep-notify", fingerprint(outage)));
+        push_line(&mut out, &format!("DTSTART:{}", start.format(ICS_DATETIME_FORMAT)));
+        push_line(&mut out, &format!("DTEND:{}", end.format(ICS_DATETIME_FORMAT)));
+        push_line(&mut out, &format!("SUMMARY:Power outage: {}", escape_text(&outage.location)));
+        push_line(
+            &mut out,
+            &format!("DESCRIPTION:{} - {}", escape_text(&outage.street), escape_text(&outage.note)),
+        );
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    fs::write(path, out)?;
+    Ok(())
+}
+
+/// Escapes a TEXT property value per RFC 5545 §3.3.11 - backslash, semicolon, comma, and
+/// newlines must be escaped before being written into a content line.
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Appends a content line, folded to RFC 5545's 75-octet limit with continuation lines
+/// prefixed by a single space, terminated with the required CRLF.
+fn push_line(out: &mut String, line: &str) {
+    out.push_str(&fold_line(line));
+}
+
+fn fold_line(line: &str) -> String {
+    if line.len() <= MAX_LINE_OCTETS {
+        return format!("{}\r\n", line);
+    }
+
+    let mut folded = String::new();
+    let mut start = 0;
+    let mut first = true;
+
+    while start < line.len() {
+        let limit = if first { MAX_LINE_OCTETS } else { MAX_LINE_OCTETS - 1 };
+        let mut end = (start + limit).min(line.len());
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        if !first {
+            folded.push(' ');
+        }
+        folded.push_str(&line[start..end]);
+        folded.push_str("\r\n");
+
+        start = end;
+        first = false;
+    }
+
+    folded
+}
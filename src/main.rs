@@ -1,10 +1,23 @@
-use chrono::{Duration, Local};
+use chrono::{DateTime, Duration, Local};
 use clap::Parser;
 use lettre::message::header::ContentType;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
-use scraper::{Html, Selector};
+use serde::Serialize;
 use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+mod config;
+mod fetch;
+mod ics;
+mod notify;
+mod server;
+mod state;
+
+use config::{Area, Config};
+use fetch::{FetchTask, RateLimiter};
+use state::StateStore;
 
 #[derive(Parser, Debug)]
 #[command(name = "hep-outage-checker")]
@@ -15,105 +28,41 @@ struct Args {
 
     #[arg(long, short = 'f', help = "Filter outages by location or street (partial match). Shows all if not provided")]
     filter: Option<String>,
-}
-
-#[derive(Debug)]
-struct PowerOutage {
-    date: String,
-    location: String,
-    street: String,
-    time: String,
-    note: String,
-}
-
-fn fetch_page(date: &str, city: &str, office: &str) -> Result<String, Box<dyn std::error::Error>> {
-    let url = format!(
-        "https://www.hep.hr/ods/bez-struje/19?dp={}&el={}&datum={}",
-        city, office, date
-    );
-
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .build()?;
 
-    let response = client.get(&url).send()?;
-    let html = response.text()?;
-
-    Ok(html)
-}
+    #[arg(long, help = "Run forever, re-checking on a fixed interval instead of exiting after one pass")]
+    watch: bool,
 
-fn parse_outages(html: &str) -> Result<Vec<PowerOutage>, Box<dyn std::error::Error>> {
-    let document = Html::parse_document(html);
-    let mut outages = Vec::new();
+    #[arg(long, default_value_t = 6, help = "Hours to wait between checks in --watch mode")]
+    interval_hours: u64,
 
-    // Find the date from the heading
-    let date_selector = Selector::parse("h3").unwrap();
-    let date = document
-        .select(&date_selector)
-        .next()
-        .map(|el| el.text().collect::<String>())
-        .unwrap_or_default();
+    #[arg(long, default_value = "hep_notify_state.json", help = "Path to the dedup state file used to avoid re-notifying the same outage (only used in --watch mode)")]
+    state_file: PathBuf,
 
-    // Each outage is in a section with hr separators
-    // Look for sections with "Mjesto:" pattern
-    let text = document.root_element().text().collect::<Vec<_>>().join(" ");
+    #[arg(long, help = "Raise desktop notifications instead of sending email")]
+    notify: bool,
 
-    // Split by horizontal rules or look for "Mjesto:" patterns
-    let lines: Vec<&str> = text.split('\n').map(|s| s.trim()).collect();
+    #[arg(long, help = "Write the scheduled outages to an iCalendar (.ics) file at this path")]
+    ics: Option<PathBuf>,
 
-    let mut current_outage: Option<PowerOutage> = None;
-    let mut expect_time_next = false;
+    #[arg(long, default_value_t = 2.0, help = "Maximum requests per second sent to hep.hr across all concurrent fetches")]
+    max_rps: f64,
 
-    for line in lines {
-        if line.starts_with("Mjesto:") {
-            if let Some(outage) = current_outage.take() {
-                outages.push(outage);
-            }
-            current_outage = Some(PowerOutage {
-                date: date.clone(),
-                location: line.replace("Mjesto:", "").trim().to_string(),
-                street: String::new(),
-                time: String::new(),
-                note: String::new(),
-            });
-            expect_time_next = false;
-        } else if line.starts_with("Ulica:") {
-            if let Some(ref mut outage) = current_outage {
-                outage.street = line.replace("Ulica:", "").trim().to_string();
-            }
-            expect_time_next = false;
-        } else if line.starts_with("Očekivano trajanje:") {
-            // The time might be on the same line or the next line
-            let time_on_same_line = line.replace("Očekivano trajanje:", "").trim().to_string();
-            if !time_on_same_line.is_empty() {
-                if let Some(ref mut outage) = current_outage {
-                    outage.time = time_on_same_line;
-                }
-                expect_time_next = false;
-            } else {
-                // Time is on the next line
-                expect_time_next = true;
-            }
-        } else if line.starts_with("Napomena:") {
-            if let Some(ref mut outage) = current_outage {
-                outage.note = line.replace("Napomena:", "").trim().to_string();
-            }
-            expect_time_next = false;
-        } else if expect_time_next && !line.is_empty() && line.contains("-") {
-            // This should be the time line (e.g., "09:00 - 11:30")
-            if let Some(ref mut outage) = current_outage {
-                outage.time = line.to_string();
-            }
-            expect_time_next = false;
-        }
-    }
-
-    // Don't forget the last one
-    if let Some(outage) = current_outage {
-        outages.push(outage);
-    }
+    #[arg(long, help = "Serve a read-only JSON/HTML dashboard of the last-scraped outages on this address, e.g. 127.0.0.1:8080")]
+    serve: Option<String>,
+}
 
-    Ok(outages)
+#[derive(Debug, Clone, Serialize)]
+struct PowerOutage {
+    area: String,
+    date: String,
+    location: String,
+    street: String,
+    time: String,
+    note: String,
+    // Structured start/end derived from `date` + `time`; `None` when either fails to parse,
+    // in which case the raw `time` string remains the only source of truth.
+    start: Option<DateTime<Local>>,
+    end: Option<DateTime<Local>>,
 }
 
 fn send_email(
@@ -124,30 +73,19 @@ fn send_email(
     smtp_password: &str,
     smtp_server: &str,
     filter: &Option<String>,
-    city: &str,
-    office: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut body = String::from("⚡ PLANNED POWER OUTAGES IN YOUR AREA ⚡\n\n");
     body.push_str(&format!("Found {} scheduled outage(s):\n\n", outages.len()));
-    
+
     for (i, outage) in outages.iter().enumerate() {
+        let summary = notify::format_outage(outage);
         body.push_str(&format!("━━━ OUTAGE {} ━━━\n", i + 1));
-        body.push_str(&format!("📅 Date: {}\n", outage.date));
-        body.push_str(&format!("📍 Location: {}\n", outage.location));
-        body.push_str(&format!("🛣️  Street: {}\n", outage.street));
-        body.push_str(&format!("⏰ Time: {}\n", outage.time));
-        if !outage.note.is_empty() {
-            body.push_str(&format!("📝 Note: {}\n", outage.note));
-        }
-        body.push('\n');
+        body.push_str(&summary.body);
+        body.push_str("\n\n");
     }
-    
+
     body.push_str("\n---\n");
     body.push_str("This is an automated notification from HEP Outage Checker\n");
-    body.push_str(&format!(
-        "Source: https://www.hep.hr/ods/bez-struje/19?dp={}&el={}\n",
-        city, office
-    ));
 
     let subject = match filter {
         Some(location) => format!("⚡ Power Outage Alert - {}", location),
@@ -188,7 +126,37 @@ fn filter_outages<'a>(outages: &'a [PowerOutage], filter: &Option<String>) -> Ve
     }
 }
 
-fn print_outages_detailed(outages: &[PowerOutage], city: &str, office: &str) {
+/// Looks up the per-area filter configured for `area_name`, used when applying it to a
+/// `FetchResult` that only carries the area's name, not the `Area` itself.
+fn find_area_filter<'a>(config: &'a Config, area_name: &str) -> &'a Option<String> {
+    static NONE: Option<String> = None;
+    config
+        .areas
+        .iter()
+        .find(|a| a.name == area_name)
+        .map(|a| &a.filter)
+        .unwrap_or(&NONE)
+}
+
+/// Same matching rules as `filter_outages`, but consumes the outages instead of borrowing them
+/// - used for a per-area filter applied before outages from different areas get aggregated.
+fn filter_outages_owned(outages: Vec<PowerOutage>, filter: &Option<String>) -> Vec<PowerOutage> {
+    match filter {
+        Some(filter_text) => {
+            let filter_lower = filter_text.to_lowercase();
+            outages
+                .into_iter()
+                .filter(|outage| {
+                    outage.location.to_lowercase().contains(&filter_lower)
+                        || outage.street.to_lowercase().contains(&filter_lower)
+                })
+                .collect()
+        }
+        None => outages,
+    }
+}
+
+fn print_outages_detailed(outages: &[PowerOutage]) {
     println!("\n╔════════════════════════════════════════════════════════════════╗");
     println!("║        ⚡ DRY RUN - POWER OUTAGE DATA (NO EMAIL SENT) ⚡        ║");
     println!("╚════════════════════════════════════════════════════════════════╝\n");
@@ -202,6 +170,7 @@ fn print_outages_detailed(outages: &[PowerOutage], city: &str, office: &str) {
 
     for (i, outage) in outages.iter().enumerate() {
         println!("━━━━━━━━━━━━━━━━━ OUTAGE {} ━━━━━━━━━━━━━━━━━", i + 1);
+        println!("🏘️  Area:     {}", outage.area);
         println!("📅 Date:     {}", outage.date);
         println!("📍 Location: {}", outage.location);
         println!("🛣️  Street:   {}", outage.street);
@@ -212,89 +181,146 @@ fn print_outages_detailed(outages: &[PowerOutage], city: &str, office: &str) {
         println!();
     }
 
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!(
-        "Source: https://www.hep.hr/ods/bez-struje/19?dp={}&el={}",
-        city, office
-    );
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    // Get environment variables (only required if not in dry-run mode)
-    let to_email = if args.dry_run {
-        String::new()
-    } else {
-        env::var("TO_EMAIL").expect("TO_EMAIL must be set")
-    };
-    let from_email = if args.dry_run {
-        String::new()
-    } else {
-        env::var("FROM_EMAIL").expect("FROM_EMAIL must be set")
-    };
-    let smtp_username = if args.dry_run {
-        String::new()
-    } else {
-        env::var("SMTP_USERNAME").expect("SMTP_USERNAME must be set")
+    let mut config = match Config::load()? {
+        Some(config) => config,
+        // First run: a template was just written. Stop here instead of scraping and
+        // emailing with its placeholder credentials.
+        None => return Ok(()),
     };
-    let smtp_password = if args.dry_run {
-        String::new()
-    } else {
-        env::var("SMTP_PASSWORD").expect("SMTP_PASSWORD must be set")
-    };
-    let smtp_server = env::var("SMTP_SERVER").unwrap_or_else(|_| "smtp.gmail.com".to_string());
 
-    // Get HEP location parameters
-    let hep_city = env::var("HEP_CITY").expect("HEP_CITY must be set");
-    let hep_office = env::var("HEP_OFFICE").expect("HEP_OFFICE must be set");
+    // Env vars remain supported as overrides on top of the config file.
+    if let Ok(v) = env::var("TO_EMAIL") {
+        config.smtp.to = v;
+    }
+    if let Ok(v) = env::var("FROM_EMAIL") {
+        config.smtp.from = v;
+    }
+    if let Ok(v) = env::var("SMTP_USERNAME") {
+        config.smtp.username = v;
+    }
+    if let Ok(v) = env::var("SMTP_PASSWORD") {
+        config.smtp.password = v;
+    }
+    if let Ok(v) = env::var("SMTP_SERVER") {
+        config.smtp.server = v;
+    }
+    if let (Ok(city), Ok(office)) = (env::var("HEP_CITY"), env::var("HEP_OFFICE")) {
+        config.areas = vec![Area {
+            name: "default".to_string(),
+            city,
+            office,
+            filter: None,
+        }];
+    }
 
     println!("🔍 HEP Outage Checker starting...");
     if args.dry_run {
         println!("🔍 Mode: DRY RUN (no email will be sent)");
+    } else if args.notify {
+        println!("🔔 Will notify: desktop notifications");
     } else {
-        println!("📧 Will notify: {}", to_email);
+        println!("📧 Will notify: {}", config.smtp.to);
     }
-    
-    // Check today and the next 7 days
+    println!(
+        "🗺️  Monitoring {} area(s): {}",
+        config.areas.len(),
+        config.areas.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ")
+    );
+
+    // Dedup only applies under --watch: a plain one-shot run keeps the long-standing
+    // "email what you find every run" behavior and never touches the state file.
+    let mut state = if args.watch {
+        StateStore::load(&args.state_file)
+    } else {
+        StateStore::default()
+    };
+
+    let snapshot: Arc<RwLock<Vec<PowerOutage>>> = Arc::new(RwLock::new(Vec::new()));
+    if let Some(addr) = args.serve.clone() {
+        server::spawn(addr, server::AppState { outages: snapshot.clone() });
+    }
+
+    loop {
+        let outages = run_cycle(&args, &config, &mut state);
+        if args.serve.is_some() {
+            *snapshot.write().unwrap() = outages;
+        }
+
+        // `--serve` needs the process to keep running to keep answering requests, so it
+        // implies the polling loop even without an explicit `--watch`.
+        if !args.watch && args.serve.is_none() {
+            break;
+        }
+
+        let interval = std::time::Duration::from_secs(args.interval_hours * 3600);
+        println!("\n💤 Sleeping for {} hour(s) until next check...", args.interval_hours);
+        std::thread::sleep(interval);
+    }
+
+    Ok(())
+}
+
+/// Runs one scrape-filter-notify pass: fetches the next 8 days of outages for every configured
+/// area concurrently (rate-limited to `args.max_rps`), in `--watch` mode dedupes against `state`
+/// so only newly-appeared outages get delivered (otherwise every match is delivered), and prunes
+/// fingerprints whose date has already passed.
+/// Returns the full (non-deduped) snapshot of matching outages, e.g. for `--serve` to publish.
+fn run_cycle(args: &Args, config: &Config, state: &mut StateStore) -> Vec<PowerOutage> {
     let today = Local::now();
+
+    let dates: Vec<String> = (0..=7)
+        .map(|days_ahead| (today + Duration::days(days_ahead)).format("%d.%m.%Y").to_string())
+        .collect();
+
+    let tasks: Vec<FetchTask> = config
+        .areas
+        .iter()
+        .flat_map(|area| {
+            dates.iter().map(move |date| FetchTask {
+                area,
+                date: date.clone(),
+            })
+        })
+        .collect();
+
+    println!(
+        "\n🚀 Fetching {} (area, date) combination(s), capped at {:.1} req/s...",
+        tasks.len(),
+        args.max_rps
+    );
+
+    let limiter = RateLimiter::new(args.max_rps);
+    let results = fetch::fetch_all(tasks, &limiter);
+
     let mut all_outages = Vec::new();
-    
-    for days_ahead in 0..=7 {
-        let check_date = today + Duration::days(days_ahead);
-        let date_str = check_date.format("%d.%m.%Y").to_string();
-        
-        println!("\n📅 Checking date: {}", date_str);
-
-        match fetch_page(&date_str, &hep_city, &hep_office) {
-            Ok(html) => {
-                match parse_outages(&html) {
-                    Ok(outages) => {
-                        if !outages.is_empty() {
-                            println!("   ⚠️  Found {} outage(s)", outages.len());
-                            for outage in &outages {
-                                println!("      - {}: {}", outage.location, outage.time);
-                            }
-                            all_outages.extend(outages);
-                        } else {
-                            println!("   ✅ No outages scheduled");
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("   ❌ Error parsing outages: {}", e);
-                    }
+    for result in results {
+        match result.outages {
+            Ok(outages) => {
+                let outages = filter_outages_owned(outages, find_area_filter(config, &result.area));
+                if !outages.is_empty() {
+                    println!(
+                        "   ⚠️  [{} / {}] Found {} outage(s)",
+                        result.area,
+                        result.date,
+                        outages.len()
+                    );
+                    all_outages.extend(outages);
                 }
             }
             Err(e) => {
-                eprintln!("   ❌ Error fetching page: {}", e);
+                eprintln!("   ❌ [{} / {}] {}", result.area, result.date, e);
             }
         }
-        
-        // Small delay to be nice to the server
-        std::thread::sleep(std::time::Duration::from_millis(500));
     }
-    
+
+    state.prune(today.date_naive());
+
     // Apply filter if provided
     let filtered_outages = filter_outages(&all_outages, &args.filter);
 
@@ -307,54 +333,75 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    let filtered_owned: Vec<PowerOutage> = filtered_outages.iter().map(|&o| o.clone()).collect();
+
+    if let Some(ics_path) = &args.ics {
+        match ics::write_ics(&filtered_owned, ics_path) {
+            Ok(_) => println!("\n📆 Wrote iCalendar file to {}", ics_path.display()),
+            Err(e) => eprintln!("\n❌ Failed to write iCalendar file: {}", e),
+        }
+    }
+
     if args.dry_run {
-        // Convert Vec<&PowerOutage> to Vec<PowerOutage> for printing
-        let outages_to_print: Vec<PowerOutage> = filtered_outages
-            .iter()
-            .map(|&outage| PowerOutage {
-                date: outage.date.clone(),
-                location: outage.location.clone(),
-                street: outage.street.clone(),
-                time: outage.time.clone(),
-                note: outage.note.clone(),
-            })
-            .collect();
-        print_outages_detailed(&outages_to_print, &hep_city, &hep_office);
+        print_outages_detailed(&filtered_owned);
+        return filtered_owned;
+    }
+
+    // Dedup only applies in --watch mode; a one-shot run always delivers everything it finds.
+    let new_outages: Vec<PowerOutage> = if args.watch {
+        state.new_outages(&filtered_owned).into_iter().cloned().collect()
     } else {
-        if !filtered_outages.is_empty() {
+        filtered_owned.clone()
+    };
+
+    if !new_outages.is_empty() {
+        // Only mark-seen-and-persist once delivery actually succeeds, so a transient SMTP
+        // error or missing D-Bus session doesn't get the outage deduped away forever.
+        let delivered = if args.notify {
+            println!("\n🔔 Raising desktop notifications...");
+            match notify::send_desktop_notifications(&new_outages) {
+                Ok(_) => {
+                    println!("✅ Notifications shown!");
+                    true
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to show notifications: {}", e);
+                    false
+                }
+            }
+        } else {
             println!("\n📧 Sending email notification...");
-            let outages_to_send: Vec<PowerOutage> = filtered_outages
-                .iter()
-                .map(|&outage| PowerOutage {
-                    date: outage.date.clone(),
-                    location: outage.location.clone(),
-                    street: outage.street.clone(),
-                    time: outage.time.clone(),
-                    note: outage.note.clone(),
-                })
-                .collect();
             match send_email(
-                &outages_to_send,
-                &to_email,
-                &from_email,
-                &smtp_username,
-                &smtp_password,
-                &smtp_server,
+                &new_outages,
+                &config.smtp.to,
+                &config.smtp.from,
+                &config.smtp.username,
+                &config.smtp.password,
+                &config.smtp.server,
                 &args.filter,
-                &hep_city,
-                &hep_office,
             ) {
-                Ok(_) => println!("✅ Email sent successfully!"),
-                Err(e) => eprintln!("❌ Failed to send email: {}", e),
+                Ok(_) => {
+                    println!("✅ Email sent successfully!");
+                    true
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to send email: {}", e);
+                    false
+                }
             }
-        } else {
-            if args.filter.is_some() {
-                println!("\n✅ No matching outages found. No email sent.");
-            } else {
-                println!("\n✅ No outages found in the next 7 days. No email sent.");
+        };
+
+        if delivered && args.watch {
+            state.mark_seen(&new_outages);
+            if let Err(e) = state.save(&args.state_file) {
+                eprintln!("   ⚠️  Failed to persist state file: {}", e);
             }
         }
+    } else if args.filter.is_some() {
+        println!("\n✅ No new matching outages found. No email sent.");
+    } else {
+        println!("\n✅ No new outages found. No email sent.");
     }
-    
-    Ok(())
+
+    filtered_owned
 }
@@ -0,0 +1,60 @@
+use notify_rust::Notification;
+
+use crate::PowerOutage;
+
+/// A formatted outage summary shared between the email body and desktop notifications, so
+/// both delivery channels describe an outage the same way.
+pub struct OutageSummary {
+    pub title: String,
+    pub body: String,
+}
+
+/// Builds the title/body pair for a single outage.
+pub fn format_outage(outage: &PowerOutage) -> OutageSummary {
+    let title = format!("⚡ Power outage — {}", outage.location);
+
+    let mut body = format!(
+        "🏘️  Area: {}\n📅 Date: {}\n📍 Location: {}\n🛣️  Street: {}\n⏰ Time: {}",
+        outage.area, outage.date, outage.location, outage.street, outage.time
+    );
+    if !outage.note.is_empty() {
+        body.push_str(&format!("\n📝 Note: {}", outage.note));
+    }
+
+    OutageSummary { title, body }
+}
+
+// Above this many outages in one cycle, raise a single batched summary instead of one
+// notification each, so a multi-area/8-day scan doesn't flood the desktop with popups.
+const MAX_INDIVIDUAL_NOTIFICATIONS: usize = 3;
+
+/// Raises desktop notifications for `outages` - an SMTP-free alternative to `send_email` for
+/// running the checker on a laptop.
+pub fn send_desktop_notifications(outages: &[PowerOutage]) -> Result<(), Box<dyn std::error::Error>> {
+    if outages.len() > MAX_INDIVIDUAL_NOTIFICATIONS {
+        return show_batch_notification(outages);
+    }
+
+    for outage in outages {
+        let summary = format_outage(outage);
+        Notification::new()
+            .summary(&summary.title)
+            .body(&summary.body)
+            .show()?;
+    }
+    Ok(())
+}
+
+/// Raises one notification summarizing every outage, used instead of one-per-outage once
+/// there are too many to pop up individually.
+fn show_batch_notification(outages: &[PowerOutage]) -> Result<(), Box<dyn std::error::Error>> {
+    let title = format!("⚡ {} power outages found", outages.len());
+    let body = outages
+        .iter()
+        .map(|outage| format!("• {} — {}", outage.location, outage.time))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Notification::new().summary(&title).body(&body).show()?;
+    Ok(())
+}
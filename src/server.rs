@@ -0,0 +1,86 @@
+use std::sync::{Arc, RwLock};
+
+use axum::extract::{Query, State};
+use axum::response::{Html, IntoResponse};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+
+use crate::{filter_outages, PowerOutage};
+
+/// Shared last-scraped snapshot the dashboard serves instantly, refreshed by the main polling
+/// loop on every cycle instead of re-scraping per request.
+#[derive(Clone)]
+pub struct AppState {
+    pub outages: Arc<RwLock<Vec<PowerOutage>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FilterParams {
+    filter: Option<String>,
+}
+
+/// Starts the read-only dashboard on its own OS thread with a dedicated async runtime, so the
+/// main scrape loop keeps running exactly as it did before `--serve` was added.
+pub fn spawn(addr: String, state: AppState) {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start dashboard runtime");
+
+        runtime.block_on(async move {
+            let app = Router::new()
+                .route("/", get(html_view))
+                .route("/outages", get(json_view))
+                .with_state(state);
+
+            let listener = tokio::net::TcpListener::bind(&addr)
+                .await
+                .unwrap_or_else(|e| panic!("failed to bind dashboard address {}: {}", addr, e));
+
+            println!("🌐 Dashboard listening on http://{}", addr);
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("❌ Dashboard server stopped: {}", e);
+            }
+        });
+    });
+}
+
+async fn json_view(State(state): State<AppState>, Query(params): Query<FilterParams>) -> impl IntoResponse {
+    let outages = state.outages.read().unwrap();
+    let filtered: Vec<PowerOutage> = filter_outages(&outages, &params.filter)
+        .into_iter()
+        .cloned()
+        .collect();
+    Json(filtered)
+}
+
+async fn html_view(State(state): State<AppState>, Query(params): Query<FilterParams>) -> Html<String> {
+    let outages = state.outages.read().unwrap();
+    let filtered = filter_outages(&outages, &params.filter);
+
+    let mut html = String::from("<html><head><title>HEP Outage Dashboard</title></head><body><pre>\n");
+    html.push_str("⚡ POWER OUTAGE DASHBOARD ⚡\n\n");
+
+    if filtered.is_empty() {
+        html.push_str("✅ No outages found.\n");
+    } else {
+        html.push_str(&format!("Found {} scheduled outage(s):\n\n", filtered.len()));
+        for (i, outage) in filtered.iter().enumerate() {
+            html.push_str(&format!("━━━━━━━━━━━━━━━━━ OUTAGE {} ━━━━━━━━━━━━━━━━━\n", i + 1));
+            html.push_str(&format!("🏘️  Area:     {}\n", outage.area));
+            html.push_str(&format!("📅 Date:     {}\n", outage.date));
+            html.push_str(&format!("📍 Location: {}\n", outage.location));
+            html.push_str(&format!("🛣️  Street:   {}\n", outage.street));
+            html.push_str(&format!("⏰ Time:     {}\n", outage.time));
+            if !outage.note.is_empty() {
+                html.push_str(&format!("📝 Note:     {}\n", outage.note));
+            }
+            html.push('\n');
+        }
+    }
+
+    html.push_str("</pre></body></html>\n");
+    Html(html)
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::PowerOutage;
+
+/// Computes a stable fingerprint for an outage from the fields that identify it uniquely,
+/// so the same outage re-scraped across runs hashes to the same value.
+pub fn fingerprint(outage: &PowerOutage) -> String {
+    let mut hasher = DefaultHasher::new();
+    outage.date.hash(&mut hasher);
+    outage.location.hash(&mut hasher);
+    outage.street.hash(&mut hasher);
+    outage.time.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Persistent record of outage fingerprints that have already been notified on, so `--watch`
+/// mode can send exactly one alert per real outage instead of re-emailing every cycle.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StateStore {
+    // fingerprint -> outage date ("%d.%m.%Y"), kept so stale entries can be pruned once the date passes
+    seen: HashMap<String, String>,
+}
+
+impl StateStore {
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Returns the subset of `outages` that haven't been seen before. Does not mark anything
+    /// as seen - call `mark_seen` once delivery actually succeeds, so a failed send doesn't
+    /// get deduped away on the next cycle.
+    pub fn new_outages<'a>(&self, outages: &'a [PowerOutage]) -> Vec<&'a PowerOutage> {
+        outages
+            .iter()
+            .filter(|outage| !self.seen.contains_key(&fingerprint(outage)))
+            .collect()
+    }
+
+    /// Records `outages` as seen, so they're excluded from future `new_outages` calls.
+    pub fn mark_seen(&mut self, outages: &[PowerOutage]) {
+        for outage in outages {
+            self.seen.insert(fingerprint(outage), outage.date.clone());
+        }
+    }
+
+    /// Drops fingerprints whose stored date has already passed, so the state file doesn't
+    /// grow without bound across a long-running `--watch` session.
+    pub fn prune(&mut self, today: NaiveDate) {
+        self.seen.retain(|_, date| {
+            match NaiveDate::parse_from_str(date, "%d.%m.%Y") {
+                Ok(parsed) => parsed >= today,
+                // Keep entries we can't parse rather than risk dropping live state.
+                Err(_) => true,
+            }
+        });
+    }
+}